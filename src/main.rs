@@ -1,12 +1,277 @@
 use serde::{Deserialize, Serialize};
 use std::{
-    net::{SocketAddr, UdpSocket},
+    collections::{BTreeMap, HashMap, VecDeque},
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
     num::NonZeroUsize,
+    thread,
+    time::Duration,
 };
 
+/// Number of bytes in the frame-length preamble written before every
+/// `MessageHeader`. Stored as a little-endian `u32` so a frame can be
+/// recognized and fully buffered before we ever hand bytes to `serde_json`.
+const FRAME_LEN_PREFIX_SIZE: usize = 4;
+
+/// Per-request send priority carried on `MessageHeader`. Lower numeric value
+/// sorts first: the scheduler always drains the lowest non-empty bucket.
+type Priority = u8;
+pub const PRIO_HIGH: Priority = 0x20;
+pub const PRIO_NORMAL: Priority = 0x40;
+pub const PRIO_BACKGROUND: Priority = 0x80;
+
+/// A frame larger than this is split across multiple wire chunks before
+/// being handed to `MessageChannel::send`, so no single send call carries
+/// more than `CHUNK_SIZE` payload bytes plus a `ChunkHeader`. At 16 KiB a
+/// full chunk is still larger than a typical UDP MTU.
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// How long `BackendClient::call` sleeps between `poll`s that turned up
+/// nothing for it. `poll` is non-blocking (`MessageChannel::try_recv`), so
+/// without this a `call` waiting on a slow reply would busy-spin a core
+/// instead of blocking like the channel's own `recv` would.
+const CALL_POLL_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Fixed binary header written before every wire chunk's payload: which
+/// request it belongs to, its position, whether it's the last chunk of the
+/// message, and how many payload bytes follow. Kept as plain binary (like
+/// the frame-length preamble) rather than JSON since it is written once per
+/// chunk and must be trivial to read off a fixed offset.
+struct ChunkHeader {
+    req_id: RequestId,
+    chunk_index: u32,
+    end_of_message: bool,
+    payload_len: u32,
+}
+const CHUNK_HEADER_SIZE: usize = 17;
+
+impl ChunkHeader {
+    fn encode(&self) -> [u8; CHUNK_HEADER_SIZE] {
+        let mut buf = [0u8; CHUNK_HEADER_SIZE];
+        buf[0..8].copy_from_slice(&self.req_id.to_le_bytes());
+        buf[8..12].copy_from_slice(&self.chunk_index.to_le_bytes());
+        buf[12] = self.end_of_message as u8;
+        buf[13..17].copy_from_slice(&self.payload_len.to_le_bytes());
+        buf
+    }
+
+    /// Decode a `ChunkHeader` from the front of `buf` and return it along
+    /// with a slice over its payload.
+    fn decode(buf: &[u8]) -> Option<(ChunkHeader, &[u8])> {
+        if buf.len() < CHUNK_HEADER_SIZE {
+            return None;
+        }
+        let header = ChunkHeader {
+            req_id: RequestId::from_le_bytes(buf[0..8].try_into().ok()?),
+            chunk_index: u32::from_le_bytes(buf[8..12].try_into().ok()?),
+            end_of_message: buf[12] != 0,
+            payload_len: u32::from_le_bytes(buf[13..17].try_into().ok()?),
+        };
+        let payload = buf.get(CHUNK_HEADER_SIZE..CHUNK_HEADER_SIZE + header.payload_len as usize)?;
+        Some((header, payload))
+    }
+}
+
+/// A growable buffer backed by a ring of independently-appended chunks
+/// rather than one contiguous `Vec`, so serializing a header and a body (or
+/// accumulating successive reads off a socket) never has to copy either one
+/// just to concatenate them onto the end of the other. `take` and `peek` are
+/// the only places a copy happens, and only across however many chunks the
+/// requested range actually spans.
+#[derive(Default)]
+pub struct BytesBuf {
+    chunks: VecDeque<Vec<u8>>,
+    len: usize,
+}
+
+impl BytesBuf {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append `data` as one more chunk, without copying it onto an existing
+    /// chunk.
+    pub fn extend(&mut self, data: Vec<u8>) {
+        if data.is_empty() {
+            return;
+        }
+        self.len += data.len();
+        self.chunks.push_back(data);
+    }
+
+    /// Copy out the first `n` bytes without removing them from the buffer.
+    /// Used to read a small fixed-size header before enough of the rest of
+    /// the chunk it belongs to has necessarily arrived.
+    pub fn peek(&self, n: usize) -> Option<Vec<u8>> {
+        if n > self.len {
+            return None;
+        }
+        let mut out = Vec::with_capacity(n);
+        for chunk in &self.chunks {
+            if out.len() >= n {
+                break;
+            }
+            let take = (n - out.len()).min(chunk.len());
+            out.extend_from_slice(&chunk[..take]);
+        }
+        Some(out)
+    }
+
+    /// Remove and return exactly `n` bytes from the front as one contiguous
+    /// `Vec<u8>`, or `None` if fewer than `n` bytes are buffered so far (the
+    /// caller should accumulate more and retry, same as `try_parse_frame`).
+    pub fn take(&mut self, n: usize) -> Option<Vec<u8>> {
+        if n > self.len {
+            return None;
+        }
+        // Common case: the requested range is exactly one already-appended
+        // chunk, so it can be handed back without copying at all.
+        if self.chunks.front().is_some_and(|c| c.len() == n) {
+            self.len -= n;
+            return self.chunks.pop_front();
+        }
+
+        // General case: accumulate (and split, if a chunk overshoots `n`)
+        // from the front until exactly `n` bytes have been taken. Handles a
+        // single oversized front chunk too -- not just multiple chunks
+        // collectively reaching `n`.
+        let mut out = Vec::with_capacity(n);
+        while out.len() < n {
+            let mut next = self.chunks.pop_front().expect("len tracks buffered bytes");
+            let need = n - out.len();
+            if next.len() <= need {
+                out.append(&mut next);
+            } else {
+                let remainder = next.split_off(need);
+                out.append(&mut next);
+                self.chunks.push_front(remainder);
+            }
+        }
+        self.len -= n;
+        Some(out)
+    }
+}
+
+/// Reassembles the wire chunks for each in-flight request id back into one
+/// complete frame, so the rest of the code only ever deals with whole frames
+/// again. `recv_buf` accumulates raw reads until a full chunk (header +
+/// payload) is available -- a no-op for `UDPChannel`, where one `recv` is
+/// already one whole chunk, but needed once a streaming transport can split
+/// a chunk across more than one read.
+#[derive(Default)]
+pub struct ChunkReassembler {
+    partial: HashMap<RequestId, Vec<u8>>,
+    recv_buf: BytesBuf,
+}
+
+impl ChunkReassembler {
+    /// Feed freshly read bytes and return the complete RPC frame for every
+    /// request whose end-of-message chunk has now fully arrived.
+    fn receive(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.recv_buf.extend(data.to_vec());
+
+        let mut frames = Vec::new();
+        while let Some(header_bytes) = self.recv_buf.peek(CHUNK_HEADER_SIZE) {
+            let payload_len =
+                u32::from_le_bytes(header_bytes[13..17].try_into().unwrap()) as usize;
+            let chunk_len = CHUNK_HEADER_SIZE + payload_len;
+            if self.recv_buf.len() < chunk_len {
+                break;
+            }
+
+            let chunk = self.recv_buf.take(chunk_len).expect("checked length above");
+            let (header, payload) = ChunkHeader::decode(&chunk).expect("length already validated");
+
+            let buf = self.partial.entry(header.req_id).or_default();
+            buf.extend_from_slice(payload);
+            if header.end_of_message {
+                frames.push(self.partial.remove(&header.req_id).unwrap());
+            }
+        }
+        frames
+    }
+}
+
+/// An enqueued frame not yet fully sent. `remaining` still owns the frame's
+/// unsent tail as a `BytesBuf` so splitting it into wire chunks happens
+/// lazily, one `CHUNK_SIZE` slice at a time in `SendQueue::next_chunk`,
+/// instead of pre-copying the whole frame into a `Vec<Vec<u8>>` up front.
+struct QueuedMessage {
+    req_id: RequestId,
+    remaining: BytesBuf,
+    total_chunks: u32,
+    next_chunk_index: u32,
+}
+
+/// Schedules outgoing chunks across priority tiers. The non-empty bucket
+/// with the lowest priority value is always serviced first; within a bucket,
+/// messages are round-robined one chunk at a time so a large transfer can't
+/// starve concurrently-queued peers of the same priority.
+#[derive(Default)]
+pub struct SendQueue {
+    buckets: BTreeMap<Priority, VecDeque<QueuedMessage>>,
+}
+
+impl SendQueue {
+    /// Enqueue `frame` under `priority`, to be split into `CHUNK_SIZE` wire
+    /// chunks tagged with `req_id` as they're sent. Takes `frame` by value
+    /// instead of `&[u8]` so the caller's already-serialized `BytesBuf`
+    /// (e.g. `BackendClient::send_buf`) moves in directly -- no copy to
+    /// collapse it into one contiguous buffer first.
+    fn enqueue(&mut self, req_id: RequestId, priority: Priority, frame: BytesBuf) {
+        let total_chunks = frame.len().div_ceil(CHUNK_SIZE).max(1) as u32;
+        self.buckets.entry(priority).or_default().push_back(QueuedMessage {
+            req_id,
+            remaining: frame,
+            total_chunks,
+            next_chunk_index: 0,
+        });
+    }
+
+    /// Pop the single next chunk to send, per the priority/round-robin
+    /// policy, or `None` if nothing is queued. Pulls exactly the next
+    /// `CHUNK_SIZE` bytes (or less, for the last chunk) off the message's
+    /// `remaining` buffer and copies that slice into a fresh `Vec` right
+    /// behind its encoded `ChunkHeader`.
+    fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        let (_, queue) = self.buckets.iter_mut().find(|(_, q)| !q.is_empty())?;
+        let mut msg = queue.pop_front()?;
+        let take_len = msg.remaining.len().min(CHUNK_SIZE);
+        let payload = msg.remaining.take(take_len).unwrap_or_default();
+        let header = ChunkHeader {
+            req_id: msg.req_id,
+            chunk_index: msg.next_chunk_index,
+            end_of_message: msg.next_chunk_index + 1 == msg.total_chunks,
+            payload_len: payload.len() as u32,
+        };
+        let mut chunk = header.encode().to_vec();
+        chunk.extend_from_slice(&payload);
+        msg.next_chunk_index += 1;
+        if !msg.remaining.is_empty() {
+            queue.push_back(msg);
+        }
+        Some(chunk)
+    }
+
+    /// Send every currently queued chunk, in scheduled order.
+    fn drain_into<C: MessageChannel>(&mut self, channel: &mut C) -> Result<(), ()> {
+        while let Some(chunk) = self.next_chunk() {
+            let sent = channel.send(&chunk).or(Err(()))?;
+            assert_eq!(sent, chunk.len());
+        }
+        Ok(())
+    }
+}
+
 // We'll assume messages are in order and never lost...
 // In this way we can send a series of messages, handle them in the other side,
 // and expect an ordered return of results.
+// `UDPChannel` doesn't actually guarantee that; pick `TcpChannel` instead if
+// the backend needs the assumption to hold.
 
 fn main() -> std::io::Result<()> {
     {
@@ -24,38 +289,69 @@ fn main() -> std::io::Result<()> {
     Ok(())
 }
 
+/// What a frame represents on the wire, mirroring the `Payload` taxonomy
+/// JSON-RPC/DAP transports use: a call expecting a reply, the reply itself,
+/// a fire-and-forget event, or a structured failure keyed to a request id.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Request,
+    Response,
+    Notification,
+    Error,
+}
+
 #[derive(Serialize, Deserialize)]
 struct MessageHeader {
     rpc: RpcName,
+    req_id: RequestId,
+    priority: Priority,
     body_size: usize,
-    is_return: bool,
+    kind: MessageKind,
 }
 type MessageName = String;
 type RpcName = String;
+/// Identifies one in-flight call so its response can be matched up out of
+/// order, the same way the DAP/LSP transports correlate requests to replies.
+type RequestId = u64;
 
-fn find_json_delimiter(buf: &[u8]) -> Option<NonZeroUsize> {
-    let mut iter = buf.iter();
-    if iter.next() == Some(&('{'.to_ascii_lowercase() as u8)) {
-        let mut count = 1;
-        let mut indent = 1;
-        for b in iter {
-            count += 1;
-            // TODO/FIXME: Need to support detecting if { or } are inside a
-            // string, or some number matches. Basically, need to parse json....
-            if &('}'.to_ascii_lowercase() as u8) == b {
-                indent -= 1;
-                if indent == 0 {
-                    return count.try_into().ok();
-                }
-            }
-        }
+/// Look for one complete length-prefixed frame at the start of `buf` and
+/// return how many bytes it occupies, its decoded header, and a slice over
+/// its body. Returns `None` if `buf` does not yet contain a full frame (the
+/// caller should accumulate more bytes and try again) rather than panicking
+/// on a short read.
+///
+/// Wire format: `[u32 LE frame_len][MessageHeader json][body]`, where
+/// `frame_len` covers everything after the 4-byte prefix (header + body).
+fn try_parse_frame(buf: &[u8]) -> Option<(NonZeroUsize, MessageHeader, &[u8])> {
+    if buf.len() < FRAME_LEN_PREFIX_SIZE {
+        return None;
     }
-    None
+    let frame_len = u32::from_le_bytes(buf[..FRAME_LEN_PREFIX_SIZE].try_into().ok()?) as usize;
+    let frame_end = FRAME_LEN_PREFIX_SIZE + frame_len;
+    if buf.len() < frame_end {
+        return None;
+    }
+    let frame = &buf[FRAME_LEN_PREFIX_SIZE..frame_end];
+
+    let mut stream = serde_json::Deserializer::from_slice(frame).into_iter::<MessageHeader>();
+    let header = stream.next()?.ok()?;
+    let header_len = stream.byte_offset();
+
+    let body_start = header_len;
+    let body_end = body_start + header.body_size;
+    let body = frame.get(body_start..body_end)?;
+
+    Some((frame_end.try_into().ok()?, header, body))
 }
 
-trait MessageChannel {
+pub trait MessageChannel {
     fn send(&mut self, buf: &[u8]) -> Result<usize, ()>;
     fn recv(&mut self, buf: &mut [u8]) -> Result<usize, ()>;
+    /// Like `recv`, but never blocks: returns `Ok(0)` immediately if nothing
+    /// has arrived yet instead of waiting for the next datagram/read, so a
+    /// caller like `BackendClient::poll` can drain whatever's buffered
+    /// without stalling.
+    fn try_recv(&mut self, buf: &mut [u8]) -> Result<usize, ()>;
 }
 
 struct UDPChannel {
@@ -74,6 +370,68 @@ impl MessageChannel for UDPChannel {
         }
         Ok(amt)
     }
+
+    fn try_recv(&mut self, buf: &mut [u8]) -> Result<usize, ()> {
+        self.socket.set_nonblocking(true).or(Err(()))?;
+        let result = self.socket.recv_from(buf);
+        self.socket.set_nonblocking(false).or(Err(()))?;
+        match result {
+            Ok((amt, dst)) => {
+                if self.dst.is_none() {
+                    self.socket.connect(dst).unwrap();
+                }
+                Ok(amt)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(_) => Err(()),
+        }
+    }
+}
+
+/// A reliable, ordered alternative to `UDPChannel` backed by a single
+/// connected `TcpStream`. Unlike a UDP datagram, one `recv` is not
+/// guaranteed to return a whole wire chunk -- `ChunkReassembler::recv_buf`
+/// is exactly the accumulation buffer that makes that safe, so `recv` here
+/// just passes along however many bytes the stream currently has ready.
+struct TcpChannel {
+    stream: TcpStream,
+}
+
+impl MessageChannel for TcpChannel {
+    fn send(&mut self, buf: &[u8]) -> Result<usize, ()> {
+        self.stream.write_all(buf).or(Err(()))?;
+        Ok(buf.len())
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, ()> {
+        self.stream.read(buf).or(Err(()))
+    }
+
+    fn try_recv(&mut self, buf: &mut [u8]) -> Result<usize, ()> {
+        self.stream.set_nonblocking(true).or(Err(()))?;
+        let result = self.stream.read(buf);
+        self.stream.set_nonblocking(false).or(Err(()))?;
+        match result {
+            Ok(amt) => Ok(amt),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(_) => Err(()),
+        }
+    }
+}
+
+/// Accept connections on `listener` one at a time, handing each peer's
+/// `TcpChannel` to `on_connect` before accepting the next -- matching a
+/// server that spawns one handler per stream. `on_connect` is expected to
+/// drive that peer to completion (e.g. via `BackendServer::handler_loop`)
+/// before this loop moves on.
+fn tcp_accept_loop(
+    listener: TcpListener,
+    mut on_connect: impl FnMut(TcpChannel),
+) -> Result<(), ()> {
+    loop {
+        let (stream, _addr) = listener.accept().or(Err(()))?;
+        on_connect(TcpChannel { stream });
+    }
 }
 
 /// For example:
@@ -105,13 +463,26 @@ pub enum BackendRpcRetVariant {
     ExampleRpc(ExampleReturn),
 }
 pub const EXAMPLE_RPC_ID: &str = &"ExampleRpc";
+
+/// Structured failure a handler reports back to the caller, carried as the
+/// body of an `Error` frame instead of collapsing every failure into `()`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: u32,
+    pub message: String,
+}
+
+/// What a `Response` or `Error` frame resolves to once parsed: either the
+/// handler's successful return value, or the structured failure it reported.
+pub type RpcResult = Result<BackendRpcRetVariant, RpcError>;
+
 /// User to implement handlers
 trait BackendRpcHandler {
-    fn handle_example_message(&mut self, msg: ExampleMessage) -> Result<ExampleReturn, ()>;
+    fn handle_example_message(&mut self, msg: ExampleMessage) -> Result<ExampleReturn, RpcError>;
     fn handle_rpc_received(
         &mut self,
         arg: BackendRpcArgVariant,
-    ) -> Result<BackendRpcRetVariant, ()> {
+    ) -> RpcResult {
         match arg {
             BackendRpcArgVariant::ExampleRpc(m) => Ok(BackendRpcRetVariant::ExampleRpc(
                 self.handle_example_message(m)?,
@@ -121,69 +492,281 @@ trait BackendRpcHandler {
 }
 
 trait BackendServiceClient {
-    fn call_example_message(&mut self, arg: ExampleMessage) -> Result<ExampleReturn, ()>;
-    fn call(&mut self, arg: &BackendRpcArgVariant) -> Result<BackendRpcRetVariant, ()>;
+    fn call_example_message(&mut self, arg: ExampleMessage) -> Result<ExampleReturn, RpcCallError>;
+    fn call(&mut self, arg: &BackendRpcArgVariant) -> Result<BackendRpcRetVariant, RpcCallError>;
+    /// Enqueue `arg` without waiting for its response, returning the
+    /// `RequestId` that the eventual reply will be keyed under. Nothing goes
+    /// out until the next `flush`. Lets a caller pipeline N calls before
+    /// collecting any results with `poll`. Uses `PRIO_NORMAL`;
+    /// see `submit_with_priority` to schedule against other in-flight sends.
+    fn submit(&mut self, arg: &BackendRpcArgVariant) -> Result<RequestId, ()> {
+        self.submit_with_priority(arg, PRIO_NORMAL)
+    }
+    fn submit_with_priority(
+        &mut self,
+        arg: &BackendRpcArgVariant,
+        priority: Priority,
+    ) -> Result<RequestId, ()>;
+    /// Enqueue `arg` as a fire-and-forget `Notification`, sent on the next
+    /// `flush`: no id is tracked in the pending-requests map, and any reply
+    /// the server mistakenly sends back is simply dropped. Uses `PRIO_NORMAL`; see
+    /// `notify_with_priority` to schedule against other in-flight sends.
+    fn notify(&mut self, arg: &BackendRpcArgVariant) -> Result<(), ()> {
+        self.notify_with_priority(arg, PRIO_NORMAL)
+    }
+    fn notify_with_priority(
+        &mut self,
+        arg: &BackendRpcArgVariant,
+        priority: Priority,
+    ) -> Result<(), ()>;
+    /// Send every chunk enqueued by `submit`/`submit_with_priority`/`notify`
+    /// so far, in priority + round-robin order.
+    fn flush(&mut self) -> Result<(), ()>;
+    /// Read whatever response frames have arrived, dispatch each to the
+    /// `RequestId` it matches in the pending-requests map, and return them,
+    /// along with anything an interleaved `call` collected earlier but
+    /// wasn't waiting on. Responses for unrecognized ids (already
+    /// collected, or not ours) are dropped. Never blocks: returns an empty
+    /// `Vec` if nothing has arrived yet. A response whose handler reported
+    /// a failure comes back as `Err` instead of being collapsed into the
+    /// outer transport-level `Result`.
+    fn poll(&mut self) -> Result<Vec<(RequestId, RpcResult)>, ()>;
+}
+
+/// Why a `BackendServiceClient::call` failed: either the underlying
+/// `MessageChannel` broke, or the request reached the server and the
+/// handler reported a structured failure.
+#[derive(Debug)]
+pub enum RpcCallError {
+    /// Failed to send or receive on the underlying `MessageChannel`.
+    Transport,
+    /// The server reported a structured failure handling the RPC.
+    Remote(RpcError),
+}
+
+impl From<()> for RpcCallError {
+    fn from(_: ()) -> Self {
+        RpcCallError::Transport
+    }
 }
 
 trait BackendService {
     fn handler_loop<H: BackendRpcHandler>(&mut self, handler: H, addr: &str) -> Result<(), ()>;
 }
-impl<C: MessageChannel> BackendService for C {
+
+/// Wraps a `MessageChannel` with the per-connection state `handler_loop`
+/// needs to chunk and prioritize its responses: a send queue and a
+/// reassembler for the chunked requests it receives.
+pub struct BackendServer<C: MessageChannel> {
+    channel: C,
+    send_queue: SendQueue,
+    reassembler: ChunkReassembler,
+    send_buf: BytesBuf,
+}
+
+impl<C: MessageChannel> BackendServer<C> {
+    pub fn new(channel: C) -> Self {
+        Self {
+            channel,
+            send_queue: SendQueue::default(),
+            reassembler: ChunkReassembler::default(),
+            send_buf: BytesBuf::default(),
+        }
+    }
+}
+
+impl<C: MessageChannel> BackendService for BackendServer<C> {
     fn handler_loop<H: BackendRpcHandler>(&mut self, mut handler: H, addr: &str) -> Result<(), ()> {
-        let mut buf = [0u8; 4096];
+        let mut buf = [0u8; CHUNK_SIZE + CHUNK_HEADER_SIZE];
         loop {
             // TODO(error_handling) Listen for message
-            let end = self.recv(&mut buf).unwrap();
+            let end = self.channel.recv(&mut buf).unwrap();
+            if end == 0 {
+                // The peer closed its end (e.g. `TcpChannel` hitting EOF) --
+                // nothing more will ever arrive on this connection, so stop
+                // driving it instead of spinning on zero-length reads.
+                return Ok(());
+            }
 
-            let mut start = 0;
-            loop {
-                if let Some((new_start, msg)) = BackendSerializer::parse_rpc_recv(&buf[start..end])
-                {
-                    start = new_start.get() + start;
-                    // TODO(error_handling): Handle unexpected fail to parse more gracefully.
-                    let res = handler
-                        .handle_rpc_received(msg)
+            for frame in self.reassembler.receive(&buf[..end]) {
+                // TODO(error_handling): Handle unexpected fail to parse more gracefully.
+                let (_consumed, req_id, priority, kind, msg) =
+                    BackendSerializer::parse_rpc_recv(&frame)
                         .expect("Unexpected failure to parse msg");
+                let result = handler.handle_rpc_received(msg);
 
-                    // TODO(error_handling): Eventually should queue up multiple returns rather than pushing individual messages.
-                    let ret_buf = BackendSerializer::serialize_rpc_ret(res);
-                    // TODO(error_handling): Gracefully handle failure to send response.
-                    let sent = self.send(&ret_buf).unwrap();
-                    assert_eq!(sent, ret_buf.len());
-                } else {
-                    break;
+                if kind == MessageKind::Notification {
+                    // Fire-and-forget: the caller isn't waiting on a
+                    // RequestId, so there's nowhere to send a reply even if
+                    // `result` is an `Err`.
+                    // TODO(error_handling): Surface failed notifications somewhere (metrics/log).
+                    continue;
+                }
+
+                // TODO(error_handling): Eventually should queue up multiple returns rather than pushing individual messages.
+                // Echo the caller's req_id and priority back so the response is
+                // matched and scheduled the same way the request was.
+                match result {
+                    Ok(res) => {
+                        BackendSerializer::serialize_rpc_ret_into(
+                            &mut self.send_buf,
+                            req_id,
+                            priority,
+                            res,
+                        );
+                    }
+                    Err(err) => {
+                        BackendSerializer::serialize_rpc_error_into(
+                            &mut self.send_buf,
+                            req_id,
+                            priority,
+                            err,
+                        );
+                    }
                 }
+                self.send_queue
+                    .enqueue(req_id, priority, std::mem::take(&mut self.send_buf));
+                // TODO(error_handling): Gracefully handle failure to send response.
+                self.send_queue.drain_into(&mut self.channel).unwrap();
             }
         }
     }
 }
 
-impl<C: MessageChannel> BackendServiceClient for C {
-    fn call_example_message(&mut self, arg: ExampleMessage) -> Result<ExampleReturn, ()> {
-        match self.call(&BackendRpcArgVariant::ExampleRpc(arg))? {
-            BackendRpcRetVariant::ExampleRpc(res) => Ok(res),
-            // TODO(error_handling): Unexpected result type received.
-            _ => Err(()),
+/// Wraps a `MessageChannel` with the bookkeeping needed to have multiple RPCs
+/// in flight at once: a monotonic id generator and a `pending_requests` map
+/// of outstanding calls (mirroring the correlation map DAP/LSP transports
+/// keep between their request and response handling), plus the send queue
+/// and reassembler needed to chunk and prioritize those RPCs.
+pub struct BackendClient<C: MessageChannel> {
+    channel: C,
+    next_request_id: RequestId,
+    pending_requests: HashMap<RequestId, ()>,
+    send_queue: SendQueue,
+    reassembler: ChunkReassembler,
+    send_buf: BytesBuf,
+    /// Responses `poll` has matched against `pending_requests` but that
+    /// `call` hasn't claimed yet because they weren't its own `req_id`.
+    /// Kept here instead of dropped so a `call` interleaved with other
+    /// outstanding `submit`s can't lose their results.
+    ready_responses: HashMap<RequestId, RpcResult>,
+}
+
+impl<C: MessageChannel> BackendClient<C> {
+    pub fn new(channel: C) -> Self {
+        Self {
+            channel,
+            next_request_id: 0,
+            pending_requests: HashMap::new(),
+            send_queue: SendQueue::default(),
+            reassembler: ChunkReassembler::default(),
+            send_buf: BytesBuf::default(),
+            ready_responses: HashMap::new(),
         }
     }
 
-    // TODO(optimization) Add a queue option to the channel to support queueing
-    // messages rather than sending one at a time. In this case, to handle the
-    // return value, we would require them to provide a handler in the queue
-    // submit() function. (Submit would then be responsible for parsing all
-    // return values and calling the correct return handlers.)
-    fn call(&mut self, arg: &BackendRpcArgVariant) -> Result<BackendRpcRetVariant, ()> {
-        self.send(&BackendSerializer::serialize_rpc_arg(arg))
-            .or(Err(()))?;
-
-        // Now wait for response. (See optimize todo on function header, no need
-        // to wait one.)
-        let mut recv_buf = [0u8; 4096];
-        let amt = self.recv(&mut recv_buf).or(Err(()))?;
-        if let Some((_new_start, msg)) = BackendSerializer::parse_rpc_result(&recv_buf[..amt]) {
-            Ok(msg)
-        } else {
-            Err(())
+    fn alloc_request_id(&mut self) -> RequestId {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        id
+    }
+}
+
+impl<C: MessageChannel> BackendServiceClient for BackendClient<C> {
+    fn call_example_message(&mut self, arg: ExampleMessage) -> Result<ExampleReturn, RpcCallError> {
+        let BackendRpcRetVariant::ExampleRpc(res) =
+            self.call(&BackendRpcArgVariant::ExampleRpc(arg))?;
+        Ok(res)
+    }
+
+    fn submit_with_priority(
+        &mut self,
+        arg: &BackendRpcArgVariant,
+        priority: Priority,
+    ) -> Result<RequestId, ()> {
+        let req_id = self.alloc_request_id();
+        BackendSerializer::serialize_rpc_arg_into(&mut self.send_buf, req_id, priority, arg);
+        self.send_queue
+            .enqueue(req_id, priority, std::mem::take(&mut self.send_buf));
+        self.pending_requests.insert(req_id, ());
+        Ok(req_id)
+    }
+
+    fn notify_with_priority(
+        &mut self,
+        arg: &BackendRpcArgVariant,
+        priority: Priority,
+    ) -> Result<(), ()> {
+        // Still allocated for chunk reassembly's sake, just never inserted
+        // into `pending_requests` -- there's no caller waiting on it.
+        let req_id = self.alloc_request_id();
+        BackendSerializer::serialize_rpc_notify_into(&mut self.send_buf, req_id, priority, arg);
+        self.send_queue
+            .enqueue(req_id, priority, std::mem::take(&mut self.send_buf));
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), ()> {
+        self.send_queue.drain_into(&mut self.channel)
+    }
+
+    fn poll(&mut self) -> Result<Vec<(RequestId, RpcResult)>, ()> {
+        let mut recv_buf = [0u8; CHUNK_SIZE + CHUNK_HEADER_SIZE];
+
+        // Surface anything `call` buffered from an earlier poll alongside
+        // whatever just arrived, so nothing collected is ever lost.
+        let mut ready: Vec<_> = self.ready_responses.drain().collect();
+        // Keep reading until the channel has nothing more buffered.
+        loop {
+            let amt = match self.channel.try_recv(&mut recv_buf) {
+                Ok(0) => break,
+                Ok(amt) => amt,
+                Err(()) => {
+                    self.ready_responses.extend(ready);
+                    return Err(());
+                }
+            };
+            for frame in self.reassembler.receive(&recv_buf[..amt]) {
+                if let Some((_consumed, req_id, _priority, result)) =
+                    BackendSerializer::parse_rpc_result(&frame)
+                {
+                    if self.pending_requests.remove(&req_id).is_some() {
+                        ready.push((req_id, result));
+                    }
+                    // TODO(error_handling): else a response for an id we don't
+                    // know about (already collected, or not ours) -- dropped.
+                }
+            }
+        }
+        Ok(ready)
+    }
+
+    // A blocking call built on `submit`/`flush`/`poll`: submit one request,
+    // flush it to the wire, then keep polling until its response (and only
+    // its response) comes back. Any other response collected along the way
+    // is buffered in `ready_responses` instead of discarded, so it's still
+    // there for a later `call`/`poll` to pick up. Buffers every non-matching
+    // result from a batch before checking whether the target arrived --
+    // returning as soon as the target is seen would drop whatever `poll`
+    // batched in after it.
+    fn call(&mut self, arg: &BackendRpcArgVariant) -> Result<BackendRpcRetVariant, RpcCallError> {
+        let req_id = self.submit(arg)?;
+        self.flush()?;
+        loop {
+            let mut target = None;
+            for (id, result) in self.poll()? {
+                if id == req_id {
+                    target = Some(result);
+                } else {
+                    self.ready_responses.insert(id, result);
+                }
+            }
+            if let Some(result) = target {
+                return result.map_err(RpcCallError::Remote);
+            }
+            // Nothing for us this round -- `poll` doesn't block, so sleep a
+            // beat rather than spinning the core until a reply shows up.
+            thread::sleep(CALL_POLL_BACKOFF);
         }
     }
 }
@@ -191,48 +774,76 @@ impl<C: MessageChannel> BackendServiceClient for C {
 pub struct BackendSerializer;
 impl BackendSerializer {
     /// Wire format:
-    /// `[MessageHeader]` | `[ExampleMessage]`
-    /// Header            | Body
-    pub fn parse_rpc_recv(buf: &[u8]) -> Option<(NonZeroUsize, BackendRpcArgVariant)> {
-        // Parse header
-        let end = find_json_delimiter(buf)?.get();
-        let header = serde_json::from_slice::<MessageHeader>(&buf[..end]).ok()?;
-        assert!(!header.is_return);
-
-        // Parse Body
-        let start = end;
-        let end = start + header.body_size;
+    /// `[u32 LE frame_len]` | `[MessageHeader]` | `[Body]`
+    /// Preamble             | Header            | Body
+    ///
+    /// `frame_len` makes framing exact instead of heuristic: the caller never
+    /// invokes `serde_json` until the full frame is buffered, so bodies may
+    /// freely contain nested `{`/`}` without confusing the reader.
+    pub fn parse_rpc_recv(
+        buf: &[u8],
+    ) -> Option<(NonZeroUsize, RequestId, Priority, MessageKind, BackendRpcArgVariant)> {
+        let (consumed, header, body) = try_parse_frame(buf)?;
+        assert!(matches!(
+            header.kind,
+            MessageKind::Request | MessageKind::Notification
+        ));
+
         let msg = match header.rpc.as_str() {
-            EXAMPLE_RPC_ID => BackendRpcArgVariant::ExampleRpc(
-                serde_json::from_slice::<ExampleMessage>(&buf[start..end]).ok()?,
-            ),
+            EXAMPLE_RPC_ID => {
+                BackendRpcArgVariant::ExampleRpc(serde_json::from_slice::<ExampleMessage>(body).ok()?)
+            }
             // TODO(error_handling)
             _ => panic!("Unexpected rpc type"),
         };
-        Some((end.try_into().ok()?, msg))
+        Some((consumed, header.req_id, header.priority, header.kind, msg))
     }
 
-    pub fn parse_rpc_result(buf: &[u8]) -> Option<(NonZeroUsize, BackendRpcRetVariant)> {
-        // Parse header
-        let end = find_json_delimiter(buf)?.get();
-        let header = serde_json::from_slice::<MessageHeader>(&buf[..end]).ok()?;
-        assert!(!header.is_return);
+    pub fn parse_rpc_result(
+        buf: &[u8],
+    ) -> Option<(NonZeroUsize, RequestId, Priority, RpcResult)> {
+        let (consumed, header, body) = try_parse_frame(buf)?;
 
-        // Parse Body
-        let start = end;
-        let end = start + header.body_size;
-        let msg = match header.rpc.as_str() {
-            EXAMPLE_RPC_ID => BackendRpcRetVariant::ExampleRpc(
-                serde_json::from_slice::<ExampleReturn>(&buf[start..end]).ok()?,
-            ),
+        let result = match header.kind {
+            MessageKind::Response => Ok(match header.rpc.as_str() {
+                EXAMPLE_RPC_ID => BackendRpcRetVariant::ExampleRpc(
+                    serde_json::from_slice::<ExampleReturn>(body).ok()?,
+                ),
+                // TODO(error_handling)
+                _ => panic!("Unexpected rpc type"),
+            }),
+            MessageKind::Error => Err(serde_json::from_slice::<RpcError>(body).ok()?),
             // TODO(error_handling)
-            _ => panic!("Unexpected rpc type"),
+            MessageKind::Request | MessageKind::Notification => {
+                panic!("Unexpected message kind on the result path")
+            }
         };
-        Some((end.try_into().ok()?, msg))
+        Some((consumed, header.req_id, header.priority, result))
+    }
+
+    /// Append `header` and `body` onto `buf` as two separate chunks --
+    /// neither is copied just to concatenate it onto the other, unlike the
+    /// old `Vec::with_capacity` + two `append`s this replaced. The caller
+    /// then moves `buf` into `SendQueue::enqueue` with `std::mem::take`,
+    /// which splits it into wire chunks lazily -- no extra copy to collapse
+    /// it into one contiguous buffer first.
+    fn serialize_into(buf: &mut BytesBuf, header: &MessageHeader, body: Vec<u8>) {
+        let mut prefixed_header = serde_json::to_vec(header).unwrap();
+        let frame_len = (prefixed_header.len() + body.len()) as u32;
+
+        let mut prefix = frame_len.to_le_bytes().to_vec();
+        prefix.append(&mut prefixed_header);
+        buf.extend(prefix);
+        buf.extend(body);
     }
 
-    pub fn serialize_rpc_arg(arg: &BackendRpcArgVariant) -> Vec<u8> {
-        let mut body;
+    pub fn serialize_rpc_arg_into(
+        buf: &mut BytesBuf,
+        req_id: RequestId,
+        priority: Priority,
+        arg: &BackendRpcArgVariant,
+    ) {
+        let body;
         let rpc_id;
         match arg {
             BackendRpcArgVariant::ExampleRpc(ref arg) => {
@@ -242,18 +853,47 @@ impl BackendSerializer {
         }
         let header = MessageHeader {
             rpc: rpc_id.to_string(),
+            req_id,
+            priority,
             body_size: body.len(),
-            is_return: false,
+            kind: MessageKind::Request,
         };
-        let mut buf = serde_json::to_vec(&header).unwrap();
-        buf.append(&mut body);
-        buf
+        Self::serialize_into(buf, &header, body);
     }
 
-    // TODO(optimization): Should support serializing into a buffer rather than
-    // allocating (multiple) vecs.
-    pub fn serialize_rpc_ret(ret: BackendRpcRetVariant) -> Vec<u8> {
-        let mut body;
+    /// Same wire shape as `serialize_rpc_arg_into`, but tagged `Notification`
+    /// so `handler_loop` dispatches it without sending any reply.
+    pub fn serialize_rpc_notify_into(
+        buf: &mut BytesBuf,
+        req_id: RequestId,
+        priority: Priority,
+        arg: &BackendRpcArgVariant,
+    ) {
+        let body;
+        let rpc_id;
+        match arg {
+            BackendRpcArgVariant::ExampleRpc(ref arg) => {
+                body = serde_json::to_vec(arg).unwrap();
+                rpc_id = EXAMPLE_RPC_ID;
+            }
+        }
+        let header = MessageHeader {
+            rpc: rpc_id.to_string(),
+            req_id,
+            priority,
+            body_size: body.len(),
+            kind: MessageKind::Notification,
+        };
+        Self::serialize_into(buf, &header, body);
+    }
+
+    pub fn serialize_rpc_ret_into(
+        buf: &mut BytesBuf,
+        req_id: RequestId,
+        priority: Priority,
+        ret: BackendRpcRetVariant,
+    ) {
+        let body;
         let rpc_id;
         match ret {
             BackendRpcRetVariant::ExampleRpc(r) => {
@@ -264,11 +904,155 @@ impl BackendSerializer {
 
         let header = MessageHeader {
             rpc: rpc_id,
+            req_id,
+            priority,
+            body_size: body.len(),
+            kind: MessageKind::Response,
+        };
+        Self::serialize_into(buf, &header, body);
+    }
+
+    /// Serialize a structured failure as an `Error` frame keyed to `req_id`,
+    /// used by `handler_loop` in place of the panic it used to hit when
+    /// `handle_rpc_received` returned `Err`.
+    pub fn serialize_rpc_error_into(
+        buf: &mut BytesBuf,
+        req_id: RequestId,
+        priority: Priority,
+        err: RpcError,
+    ) {
+        let body = serde_json::to_vec(&err).unwrap();
+        let header = MessageHeader {
+            rpc: String::new(),
+            req_id,
+            priority,
             body_size: body.len(),
-            is_return: false,
+            kind: MessageKind::Error,
         };
-        let mut msg = serde_json::to_vec(&header).unwrap();
-        msg.append(&mut body);
-        return msg;
+        Self::serialize_into(buf, &header, body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_parse_frame_returns_none_on_a_short_buffer() {
+        let mut buf = BytesBuf::default();
+        BackendSerializer::serialize_rpc_arg_into(
+            &mut buf,
+            1,
+            PRIO_NORMAL,
+            &BackendRpcArgVariant::ExampleRpc(ExampleMessage {
+                msg: "hi".to_string(),
+            }),
+        );
+        let frame = buf.take(buf.len()).unwrap();
+
+        assert!(try_parse_frame(&frame[..frame.len() - 1]).is_none());
+        assert!(try_parse_frame(&frame).is_some());
+    }
+
+    #[test]
+    fn a_body_containing_braces_round_trips_via_the_exact_length_prefix() {
+        let mut buf = BytesBuf::default();
+        let msg = ExampleMessage {
+            msg: "{\"nested\": {\"a\": 1}}".to_string(),
+        };
+        BackendSerializer::serialize_rpc_arg_into(
+            &mut buf,
+            2,
+            PRIO_NORMAL,
+            &BackendRpcArgVariant::ExampleRpc(msg),
+        );
+        let frame = buf.take(buf.len()).unwrap();
+
+        let (_consumed, req_id, _priority, _kind, parsed) =
+            BackendSerializer::parse_rpc_recv(&frame).unwrap();
+        assert_eq!(req_id, 2);
+        let BackendRpcArgVariant::ExampleRpc(parsed) = parsed;
+        assert_eq!(parsed.msg, "{\"nested\": {\"a\": 1}}");
+    }
+
+    #[test]
+    fn chunk_reassembler_accumulates_a_chunk_split_across_two_reads() {
+        let mut send_buf = BytesBuf::default();
+        BackendSerializer::serialize_rpc_arg_into(
+            &mut send_buf,
+            3,
+            PRIO_NORMAL,
+            &BackendRpcArgVariant::ExampleRpc(ExampleMessage {
+                msg: "split me".to_string(),
+            }),
+        );
+        let mut queue = SendQueue::default();
+        queue.enqueue(3, PRIO_NORMAL, send_buf);
+        let chunk = queue.next_chunk().unwrap();
+
+        let mut reassembler = ChunkReassembler::default();
+        let mid = chunk.len() / 2;
+        assert!(reassembler.receive(&chunk[..mid]).is_empty());
+        let frames = reassembler.receive(&chunk[mid..]);
+        assert_eq!(frames.len(), 1);
+
+        let (_consumed, req_id, _priority, _kind, parsed) =
+            BackendSerializer::parse_rpc_recv(&frames[0]).unwrap();
+        assert_eq!(req_id, 3);
+        let BackendRpcArgVariant::ExampleRpc(parsed) = parsed;
+        assert_eq!(parsed.msg, "split me");
+    }
+
+    #[test]
+    fn send_queue_services_a_higher_priority_message_before_a_large_lower_priority_one() {
+        let mut queue = SendQueue::default();
+
+        let mut large = BytesBuf::default();
+        large.extend(vec![b'x'; CHUNK_SIZE + 10]);
+        queue.enqueue(1, PRIO_BACKGROUND, large);
+
+        let mut small = BytesBuf::default();
+        small.extend(vec![b'y'; 5]);
+        queue.enqueue(2, PRIO_HIGH, small);
+
+        let (header, _payload) = ChunkHeader::decode(&queue.next_chunk().unwrap()).unwrap();
+        assert_eq!(header.req_id, 2);
+        assert!(header.end_of_message);
+
+        let (header, _payload) = ChunkHeader::decode(&queue.next_chunk().unwrap()).unwrap();
+        assert_eq!(header.req_id, 1);
+        assert!(!header.end_of_message);
+
+        let (header, _payload) = ChunkHeader::decode(&queue.next_chunk().unwrap()).unwrap();
+        assert_eq!(header.req_id, 1);
+        assert!(header.end_of_message);
+
+        assert!(queue.next_chunk().is_none());
+    }
+
+    #[test]
+    fn an_error_frame_round_trips_as_a_structured_failure() {
+        let mut buf = BytesBuf::default();
+        BackendSerializer::serialize_rpc_error_into(
+            &mut buf,
+            4,
+            PRIO_NORMAL,
+            RpcError {
+                code: 7,
+                message: "nope".to_string(),
+            },
+        );
+        let frame = buf.take(buf.len()).unwrap();
+
+        let (_consumed, req_id, _priority, result) =
+            BackendSerializer::parse_rpc_result(&frame).unwrap();
+        assert_eq!(req_id, 4);
+        match result {
+            Err(err) => {
+                assert_eq!(err.code, 7);
+                assert_eq!(err.message, "nope");
+            }
+            Ok(_) => panic!("expected an Error frame to parse as Err"),
+        }
     }
 }